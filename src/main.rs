@@ -1,33 +1,161 @@
 use std::collections::HashMap;
-use std::fs::{self};
+use std::env;
 use std::io::{self, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
 use serde::{Deserialize, Serialize};
 
+mod server;
+mod storage;
+
+use storage::{FileStorage, Storage};
+
+// Address the optional WebSocket API server listens on.
+const SERVER_ADDR: &str = "127.0.0.1:9001";
+
+// Epoch offset used when computing the "Hot" ranking score, so fresh posts
+// start close to zero instead of a huge unix timestamp.
+const HOT_EPOCH_OFFSET: i64 = 1_700_000_000;
+
+// The different ways posts can be ordered when displayed, mirroring a
+// link-aggregator front page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SortMode {
+    New,   // Most recently created first
+    Top,   // Highest (likes - dislikes) score first
+    Hot,   // Reddit-style time-decayed score
+}
+
+// A single comment on a post. `parent_id` is `None` for a top-level comment
+// and `Some(id)` of another comment on the same post for a reply, so a whole
+// discussion tree can be reconstructed from this flat list.
+#[derive(Debug, Serialize, Deserialize)]
+struct Comment {
+    id: usize,                  // Unique identifier for the comment, scoped to its post
+    content: String,            // The text of the comment
+    parent_id: Option<usize>,   // The comment this one replies to, if any
+    author_id: usize,           // ID of the user who wrote the comment
+    created_at: i64,            // Unix timestamp of when the comment was created
+}
+
 // Represents a social media post with attributes for its ID, content, comments, likes, and dislikes.
 #[derive(Debug, Serialize, Deserialize)]
 struct Post {                       //Structs are used to create custom data types.
     id: usize,                      // Unique identifier for the post
     content: String,                // The content of the post
-    comments: Vec<String>,          // A list of comments made on the post
+    comments: Vec<Comment>,         // A flat list of comments made on the post, threaded via `parent_id`
+    #[serde(default = "first_comment_id")]
+    next_comment_id: usize,         // Keeps track of the next available comment ID for this post
     likes: usize,                   // Number of likes the post has received
     dislikes: usize,                // Number of dislikes the post has received
+    #[serde(default)]
+    created_at: i64,                // Unix timestamp of when the post was created
+    author_id: usize,               // ID of the user who created the post
+    community_id: usize,            // ID of the community this post was made in
+    #[serde(default)]
+    edit_history: Vec<String>,      // Previous versions of `content`, oldest first
+    #[serde(default)]
+    updated_at: Option<i64>,        // Unix timestamp of the most recent edit, if any
+    #[serde(default)]
+    reposts: usize,                 // Number of times the post has been reposted/shared
+    #[serde(default)]
+    shared_by: Vec<usize>,          // IDs of the users who reposted this post
+}
+
+// Default starting value for `next_comment_id` when loading older save files.
+fn first_comment_id() -> usize {
+    1
 }
 
 impl Post {
-    // Constructs a new Post instance with a given ID and content.
-    fn new(id: usize, content: String) -> Post {
+    // Constructs a new Post instance with a given ID, content, author, and community.
+    fn new(id: usize, content: String, author_id: usize, community_id: usize) -> Post {
         Post {
             id,
             content,
             comments: Vec::new(),    // Initializes comments as an empty vector
+            next_comment_id: 1,       // Starts comment ID counting from 1
             likes: 0,                // Sets initial likes to 0
             dislikes: 0,             // Sets initial dislikes to 0
+            created_at: current_unix_timestamp(), // Records creation time
+            author_id,                // Records who created the post
+            community_id,              // Records which community the post belongs to
+            edit_history: Vec::new(), // Starts with no prior revisions
+            updated_at: None,          // Hasn't been edited yet
+            reposts: 0,                // Starts with no reposts
+            shared_by: Vec::new(),     // Starts with no one having reposted it
         }
     }
 
-    // Adds a new comment to the post.
-    fn add_comment(&mut self, comment: String) {
-        self.comments.push(comment);  // Pushes the new comment onto the comments vector
+    // Replaces the post's content, pushing the previous content onto the
+    // edit history and stamping the edit time.
+    fn edit(&mut self, new_content: String) {
+        let previous_content = std::mem::replace(&mut self.content, new_content);
+        self.edit_history.push(previous_content);
+        self.updated_at = Some(current_unix_timestamp());
+    }
+
+    // Records a repost by the given user, incrementing the public repost
+    // counter and noting who reposted it.
+    fn repost(&mut self, user_id: usize) {
+        self.reposts += 1;               // Increases the repost count by 1
+        self.shared_by.push(user_id);    // Records who reposted it
+    }
+
+    // Raw engagement score: likes minus dislikes.
+    fn score(&self) -> i64 {
+        self.likes as i64 - self.dislikes as i64
+    }
+
+    // Reddit-style time-decayed ranking value: a logarithmic score component
+    // that rewards being well-liked, plus a linear component that rewards
+    // being recent, so fresh, well-liked posts float to the top and decay
+    // over time.
+    fn hot_rank(&self) -> f64 {
+        let score = self.score();
+        let order = (score.unsigned_abs().max(1) as f64).log10();
+        let sign = match score.cmp(&0) {
+            std::cmp::Ordering::Greater => 1.0,
+            std::cmp::Ordering::Less => -1.0,
+            std::cmp::Ordering::Equal => 0.0,
+        };
+        let seconds = (self.created_at - HOT_EPOCH_OFFSET) as f64;
+        sign * order + seconds / 45000.0
+    }
+
+    // Adds a new comment or reply to the post, returning the new comment's ID.
+    // `parent_id` is `None` for a top-level comment, or `Some` of an existing
+    // comment on this post to reply to it.
+    fn add_reply(&mut self, content: String, parent_id: Option<usize>, author_id: usize) -> usize {
+        let id = self.next_comment_id;
+        self.comments.push(Comment {
+            id,
+            content,
+            parent_id,
+            author_id,
+            created_at: current_unix_timestamp(),
+        });
+        self.next_comment_id += 1;
+        id
+    }
+
+    // Prints the comment tree for this post, indenting replies under their parent.
+    fn display_comments(&self) {
+        self.display_comment_children(None, 0);
+    }
+
+    // Recursively prints every comment whose `parent_id` matches `parent`,
+    // then their replies indented one level deeper.
+    fn display_comment_children(&self, parent: Option<usize>, depth: usize) {
+        for comment in self.comments.iter().filter(|c| c.parent_id == parent) {
+            println!(
+                "{}[{}] (user {}) {}",
+                "  ".repeat(depth),
+                comment.id,
+                comment.author_id,
+                comment.content
+            );
+            self.display_comment_children(Some(comment.id), depth + 1);
+        }
     }
 
     // Increments the like count for the post.
@@ -46,83 +174,315 @@ impl Post {
     }
 }
 
+// Returns the current time as a unix timestamp, used to stamp new posts.
+fn current_unix_timestamp() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+// A registered account. Passwords are never stored in plain text, only as a
+// bcrypt hash.
+#[derive(Debug, Serialize, Deserialize)]
+struct User {
+    id: usize,              // Unique identifier for the user
+    username: String,       // Display/login name, must be unique
+    password_hash: String,  // Bcrypt hash of the user's password
+    #[serde(default)]
+    favorites: Vec<usize>,  // IDs of posts this user has personally bookmarked
+}
+
+// The set of topics a community can be classified under, seeded so new
+// communities are grouped consistently rather than free text.
+const DEFAULT_CATEGORIES: &[&str] = &[
+    "Discussion",
+    "Gaming",
+    "Music",
+    "Science/Technology",
+    "News",
+    "Sports",
+    "Movies & TV",
+];
+
+// A topic-based grouping that posts belong to, similar to a subreddit.
+#[derive(Debug, Serialize, Deserialize)]
+struct Community {
+    id: usize,          // Unique identifier for the community
+    name: String,       // Display name of the community
+    category: String,   // One of the seeded categories this community falls under
+}
+
 // Manages a collection of posts, providing functionalities to manipulate them.
 #[derive(Serialize, Deserialize)]
 struct PostManager {
     posts: HashMap<usize, Post>,    // Stores posts with their unique IDs as keys
     next_id: usize,                  // Keeps track of the next available post ID
+    users: HashMap<usize, User>,    // Stores registered users with their unique IDs as keys
+    next_user_id: usize,             // Keeps track of the next available user ID
+    #[serde(skip)]
+    current_user: Option<usize>,    // ID of the user currently logged in this session, if any
+    #[serde(default)]
+    communities: HashMap<usize, Community>, // Stores communities with their unique IDs as keys
+    #[serde(default = "first_community_id")]
+    next_community_id: usize,        // Keeps track of the next available community ID
+    // Not part of the persisted state itself, just how it gets persisted.
+    #[serde(skip, default = "default_storage")]
+    storage: Box<dyn Storage>,
+}
+
+// Default storage backend: a JSON file named `posts.json` in the working directory.
+fn default_storage() -> Box<dyn Storage> {
+    Box::new(FileStorage::default())
+}
+
+// Default starting value for `next_community_id` when loading older save
+// files that predate communities.
+fn first_community_id() -> usize {
+    1
 }
 
 impl PostManager {
-    // Creates a new PostManager instance, loading existing posts from a file if available.
+    // Creates a new PostManager instance, loading existing posts through the
+    // default (JSON file) storage backend if available.
     fn new() -> PostManager {
-        // Try loading data from the saved file, if it exists
-        if let Ok(data) = fs::read_to_string("posts.json") {
-            if let Ok(post_manager) = serde_json::from_str(&data) {
-                return post_manager;  // Returns the loaded post manager
-            }
+        PostManager::with_storage(Box::new(FileStorage::default()))
+    }
+
+    // Creates a new PostManager using the given storage backend, loading
+    // existing state through it if available. Lets callers (such as tests)
+    // swap in a different backend than the default file.
+    fn with_storage(storage: Box<dyn Storage>) -> PostManager {
+        // Try loading data through the storage backend, if it has any
+        if let Some(mut post_manager) = storage.load() {
+            post_manager.storage = storage; // Loaded state doesn't carry a backend; attach this one
+            return post_manager;
         }
         // If loading fails, create a new instance
         PostManager {
             posts: HashMap::new(),    // Initializes with an empty HashMap
             next_id: 1,                // Starts ID counting from 1
+            users: HashMap::new(),    // Starts with no registered users
+            next_user_id: 1,           // Starts user ID counting from 1
+            current_user: None,        // No one is logged in yet
+            communities: HashMap::new(), // Starts with no communities
+            next_community_id: 1,       // Starts community ID counting from 1
+            storage,                    // Use the requested backend for future saves
         }
     }
 
-    // Saves the current posts to a JSON file for persistence.
-    fn save_data(&self) {
-        // Save the data to a JSON file
-        if let Ok(json_data) = serde_json::to_string(&self) {
-            let _ = fs::write("posts.json", json_data);  // Writes JSON data to the file
+    // Creates a new community under one of the seeded categories, returning
+    // its ID. Fails if the category isn't one of `DEFAULT_CATEGORIES`.
+    fn create_community(&mut self, name: String, category: String) -> Option<usize> {
+        if !DEFAULT_CATEGORIES.contains(&category.as_str()) {
+            println!("'{}' is not a recognized category.", category); // Error message for unknown category
+            return None;
+        }
+
+        let community = Community {
+            id: self.next_community_id,
+            name: name.clone(),
+            category,
+        };
+        self.communities.insert(self.next_community_id, community); // Inserts the new community
+        self.next_community_id += 1;                                 // Increments the next ID for future communities
+        self.save_data();                                             // Save after creating a community
+        println!("Created community '{}'.", name);                   // Confirmation message
+        Some(self.next_community_id - 1)                              // Returns the ID of the newly created community
+    }
+
+    // Lists every community along with its category.
+    fn list_communities(&self) {
+        for community in self.communities.values() {        // Iterates over all communities
+            println!(
+                "[{}] {} ({})",
+                community.id, community.name, community.category
+            );
+        }
+    }
+
+    // Registers a new user with a bcrypt-hashed password. Fails if the
+    // username is already taken.
+    fn register(&mut self, username: String, password: String) -> bool {
+        if self.users.values().any(|u| u.username == username) {
+            println!("Username '{}' is already taken.", username); // Error message for duplicate username
+            return false;
+        }
+
+        let password_hash = match bcrypt::hash(&password, bcrypt::DEFAULT_COST) {
+            Ok(hash) => hash,
+            Err(_) => {
+                println!("Failed to hash password."); // Error message if hashing fails
+                return false;
+            }
+        };
+
+        let user = User {
+            id: self.next_user_id,
+            username: username.clone(),
+            password_hash,
+            favorites: Vec::new(), // Starts with no saved posts
+        };
+        self.users.insert(self.next_user_id, user); // Inserts the new user into the HashMap
+        self.next_user_id += 1;                      // Increments the next ID for future users
+        self.save_data();                             // Save after registering
+        println!("Registered user '{}'.", username);  // Confirmation message
+        true
+    }
+
+    // Logs a user in by verifying their password, storing their ID as the
+    // current session on success.
+    fn login(&mut self, username: String, password: String) -> bool {
+        let user = self.users.values().find(|u| u.username == username);
+        match user {
+            Some(user) => match bcrypt::verify(&password, &user.password_hash) {
+                Ok(true) => {
+                    self.current_user = Some(user.id); // Starts the session for this user
+                    println!("Logged in as '{}'.", username); // Confirmation message
+                    true
+                }
+                _ => {
+                    println!("Invalid username or password."); // Error message for bad credentials
+                    false
+                }
+            },
+            None => {
+                println!("Invalid username or password."); // Error message for unknown username
+                false
+            }
         }
     }
 
-    // Creates a new post and adds it to the collection, returning its ID.
-    fn create_post(&mut self, content: String) -> usize {
-        let post = Post::new(self.next_id, content);  // Creates a new Post
+    // Saves the current state through the configured storage backend.
+    fn save_data(&self) {
+        self.storage.save(self);
+    }
+
+    // Creates a new post within a community, adding it to the collection and
+    // returning its ID. Requires a logged-in user, who becomes the post's
+    // author, and an existing community to post into.
+    fn create_post(&mut self, content: String, community_id: usize) -> Option<usize> {
+        let author_id = match self.current_user {
+            Some(id) => id,
+            None => {
+                println!("You must be logged in to create a post."); // Error message for anonymous writes
+                return None;
+            }
+        };
+
+        if !self.communities.contains_key(&community_id) {
+            println!("Community with ID {} does not exist.", community_id); // Error message for unknown community
+            return None;
+        }
+
+        let post = Post::new(self.next_id, content, author_id, community_id); // Creates a new Post
         self.posts.insert(self.next_id, post);         // Inserts the new post into the HashMap
         self.next_id += 1;                             // Increments the next ID for future posts
         self.save_data();                              // Save after creating a post
-        self.next_id - 1                               // Returns the ID of the newly created post
+        Some(self.next_id - 1)                          // Returns the ID of the newly created post
     }
 
-    // Adds a comment to an existing post by its ID.
-    fn add_comment(&mut self, post_id: usize, comment: String) {
-        if let Some(post) = self.posts.get_mut(&post_id) {  // Checks if the post exists
-            post.add_comment(comment);                       // Adds the comment to the post
-            self.save_data();                                // Save after adding a comment
-        } else {
-            println!("Post with ID {} does not exist.", post_id); // Error message if the post is not found
+    // Adds a comment or threaded reply to an existing post by its ID.
+    // `parent_id` is `None` for a top-level comment. Requires a logged-in
+    // user. Returns whether the comment was actually added.
+    fn add_reply(&mut self, post_id: usize, parent_id: Option<usize>, content: String) -> bool {
+        let author_id = match self.current_user {
+            Some(id) => id,
+            None => {
+                println!("You must be logged in to comment."); // Error message for anonymous writes
+                return false;
+            }
+        };
+
+        match self.posts.get_mut(&post_id) {
+            Some(post) => {
+                if parent_id.is_some_and(|id| !post.comments.iter().any(|c| c.id == id)) {
+                    println!("Comment with ID {:?} does not exist on this post.", parent_id); // Error for bad parent
+                    return false;
+                }
+                post.add_reply(content, parent_id, author_id); // Adds the comment/reply to the post
+                self.save_data();                              // Save after adding a comment
+                true
+            }
+            None => {
+                println!("Post with ID {} does not exist.", post_id); // Error message if the post is not found
+                false
+            }
         }
     }
 
-    // Likes a post identified by its ID.
-    fn like_post(&mut self, post_id: usize) {
+    // Prints the threaded comment tree for a post.
+    fn display_comments(&self, post_id: usize) {
+        match self.posts.get(&post_id) {
+            Some(post) => post.display_comments(),
+            None => println!("Post with ID {} does not exist.", post_id), // Error message if the post is not found
+        }
+    }
+
+    // Likes a post identified by its ID. Returns whether it existed.
+    fn like_post(&mut self, post_id: usize) -> bool {
         if let Some(post) = self.posts.get_mut(&post_id) {  // Checks if the post exists
             post.like();                                    // Increments the like count
             self.save_data();                               // Save after liking
+            true
         } else {
             println!("Post with ID {} does not exist.", post_id); // Error message if the post is not found
+            false
         }
     }
 
-    // Dislikes a post identified by its ID.
-    fn dislike_post(&mut self, post_id: usize) {
+    // Dislikes a post identified by its ID. Returns whether it existed.
+    fn dislike_post(&mut self, post_id: usize) -> bool {
         if let Some(post) = self.posts.get_mut(&post_id) {  // Checks if the post exists
             post.dislike();                                   // Increments the dislike count
             self.save_data();                                 // Save after disliking
+            true
         } else {
             println!("Post with ID {} does not exist.", post_id); // Error message if the post is not found
+            false
         }
     }
 
-    // Deletes a post identified by its ID.
-    fn delete_post(&mut self, post_id: usize) {
-        if self.posts.remove(&post_id).is_some() {         // Attempts to remove the post
-            println!("Post with ID {} has been deleted.", post_id); // Confirmation message
-            self.save_data();                               // Save after deleting
-        } else {
-            println!("Post with ID {} does not exist.", post_id); // Error message if the post is not found
+    // Deletes a post identified by its ID. Only the post's author may delete
+    // it. Returns whether the post was actually deleted.
+    fn delete_post(&mut self, post_id: usize) -> bool {
+        match self.posts.get(&post_id) {
+            Some(post) if Some(post.author_id) != self.current_user => {
+                println!("You do not have permission to delete this post."); // Error message for non-owners
+                false
+            }
+            Some(_) => {
+                self.posts.remove(&post_id);                    // Removes the post
+                println!("Post with ID {} has been deleted.", post_id); // Confirmation message
+                self.save_data();                                // Save after deleting
+                true
+            }
+            None => {
+                println!("Post with ID {} does not exist.", post_id); // Error message if the post is not found
+                false
+            }
+        }
+    }
+
+    // Edits a post's content. Only the post's author may edit it; the
+    // previous content is preserved in the edit history. Returns whether the
+    // edit was actually applied.
+    fn edit_post(&mut self, post_id: usize, new_content: String) -> bool {
+        match self.posts.get_mut(&post_id) {
+            Some(post) if Some(post.author_id) != self.current_user => {
+                println!("You do not have permission to edit this post."); // Error message for non-owners
+                false
+            }
+            Some(post) => {
+                post.edit(new_content);                          // Records the edit
+                println!("Post with ID {} has been updated.", post_id); // Confirmation message
+                self.save_data();                                 // Save after editing
+                true
+            }
+            None => {
+                println!("Post with ID {} does not exist.", post_id); // Error message if the post is not found
+                false
+            }
         }
     }
 
@@ -135,29 +495,177 @@ impl PostManager {
         }
     }
 
-    // Displays all the posts currently managed.
-    fn display_posts(&self) {
-        for post in self.posts.values() {                  // Iterates over all posts
-            println!("{:?}", post);                        // Prints each post's debug representation
+    // Reposts a post, incrementing its public repost counter and recording
+    // the current user as having shared it. Requires a logged-in user.
+    // Returns whether the repost was actually recorded.
+    fn repost_post(&mut self, post_id: usize) -> bool {
+        let user_id = match self.current_user {
+            Some(id) => id,
+            None => {
+                println!("You must be logged in to repost."); // Error message for anonymous writes
+                return false;
+            }
+        };
+
+        match self.posts.get_mut(&post_id) {
+            Some(post) => {
+                post.repost(user_id);                           // Records the repost
+                println!("Post with ID {} has been reposted.", post_id); // Confirmation message
+                self.save_data();                                // Save after reposting
+                true
+            }
+            None => {
+                println!("Post with ID {} does not exist.", post_id); // Error message if the post is not found
+                false
+            }
+        }
+    }
+
+    // Saves a post to the current user's private favorites list. Distinct
+    // from likes: a personal bookmark rather than a public engagement metric.
+    // Returns whether the favorite was actually recorded.
+    fn favorite_post(&mut self, post_id: usize) -> bool {
+        let user_id = match self.current_user {
+            Some(id) => id,
+            None => {
+                println!("You must be logged in to favorite a post."); // Error message for anonymous writes
+                return false;
+            }
+        };
+
+        if !self.posts.contains_key(&post_id) {
+            println!("Post with ID {} does not exist.", post_id); // Error message if the post is not found
+            return false;
+        }
+
+        let user = match self.users.get_mut(&user_id) {
+            Some(user) => user,
+            None => return false,
+        };
+        if user.favorites.contains(&post_id) {
+            println!("Post with ID {} is already in your favorites.", post_id); // Error message for duplicates
+            return false;
+        }
+        user.favorites.push(post_id);                           // Records the favorite
+        println!("Post with ID {} added to your favorites.", post_id); // Confirmation message
+        self.save_data();                                        // Save after favoriting
+        true
+    }
+
+    // Removes a post from the current user's private favorites list. Returns
+    // whether it was actually removed.
+    fn unfavorite_post(&mut self, post_id: usize) -> bool {
+        let user_id = match self.current_user {
+            Some(id) => id,
+            None => {
+                println!("You must be logged in to unfavorite a post."); // Error message for anonymous writes
+                return false;
+            }
+        };
+
+        let user = match self.users.get_mut(&user_id) {
+            Some(user) => user,
+            None => return false,
+        };
+        let was_favorited = user.favorites.len();
+        user.favorites.retain(|&id| id != post_id);              // Removes the post, if present
+        if user.favorites.len() == was_favorited {
+            println!("Post with ID {} is not in your favorites.", post_id); // Error message if it wasn't saved
+            return false;
+        }
+        println!("Post with ID {} removed from your favorites.", post_id); // Confirmation message
+        self.save_data();                                         // Save after unfavoriting
+        true
+    }
+
+    // Lists the current user's saved posts.
+    fn list_favorites(&self) {
+        let user_id = match self.current_user {
+            Some(id) => id,
+            None => {
+                println!("You must be logged in to view your favorites."); // Error message for anonymous reads
+                return;
+            }
+        };
+
+        let user = match self.users.get(&user_id) {
+            Some(user) => user,
+            None => return,
+        };
+        for post_id in &user.favorites {                         // Iterates over saved post IDs
+            if let Some(post) = self.posts.get(post_id) {
+                println!("{:?}", post);                          // Prints the saved post
+            }
+        }
+    }
+
+    // Displays posts ordered according to the given sort mode, like a
+    // link-aggregator front page ("New", "Top", or time-decayed "Hot").
+    // When `community_id` is given, only posts from that community are shown.
+    fn display_posts_sorted(&self, mode: SortMode, community_id: Option<usize>) {
+        let mut posts: Vec<&Post> = self
+            .posts
+            .values()
+            .filter(|p| community_id.is_none_or(|id| p.community_id == id))
+            .collect();
+
+        match mode {
+            SortMode::New => posts.sort_by_key(|p| std::cmp::Reverse(p.created_at)),
+            SortMode::Top => posts.sort_by_key(|p| std::cmp::Reverse(p.score())),
+            SortMode::Hot => posts.sort_by(|a, b| {
+                b.hot_rank()
+                    .partial_cmp(&a.hot_rank())
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            }),
+        }
+
+        for post in posts {                                 // Iterates in the chosen order
+            println!("{:?}", post);                         // Prints each post's debug representation
+            if post.updated_at.is_some() {
+                println!(
+                    "  (edited, {} revision{})",
+                    post.edit_history.len(),
+                    if post.edit_history.len() == 1 { "" } else { "s" }
+                );
+            }
         }
     }
 }
 
-// The entry point of the application.
+// The entry point of the application. Runs the interactive stdin menu by
+// default, or the WebSocket API server when launched with `--server`.
 fn main() {
+    if env::args().any(|arg| arg == "--server") {
+        let runtime = tokio::runtime::Runtime::new().expect("failed to start async runtime");
+        if let Err(e) = runtime.block_on(server::run(SERVER_ADDR)) {
+            eprintln!("Server error: {}", e);
+        }
+        return;
+    }
+
     let mut post_manager = PostManager::new();          // Initializes the PostManager
 
     loop {
         // Display menu options to the user
         println!("\nSelect an action:");
-        println!("1. Create a Post");
-        println!("2. Add Comment to a Post");
-        println!("3. Like a Post");
-        println!("4. Dislike a Post");
-        println!("5. Share a Post");
-        println!("6. Display All Posts");
-        println!("7. Delete a Post");
-        println!("8. Exit");
+        println!("1. Register");
+        println!("2. Login");
+        println!("3. Create a Community");
+        println!("4. List Communities");
+        println!("5. Create a Post");
+        println!("6. Add Comment to a Post");
+        println!("7. View Comments on a Post");
+        println!("8. Like a Post");
+        println!("9. Dislike a Post");
+        println!("10. Share a Post");
+        println!("11. Display Posts");
+        println!("12. Edit a Post");
+        println!("13. Delete a Post");
+        println!("14. Repost a Post");
+        println!("15. Favorite a Post");
+        println!("16. Unfavorite a Post");
+        println!("17. My Favorites");
+        println!("18. Exit");
 
         print!("Enter your choice: ");
         io::stdout().flush().unwrap(); // Ensure the prompt is printed before input
@@ -168,14 +676,67 @@ fn main() {
 
         match choice {
             1 => {
+                print!("Choose a username: ");
+                io::stdout().flush().unwrap(); // Prompt for username
+                let mut username = String::new();
+                io::stdin().read_line(&mut username).unwrap(); // Reads the username
+
+                print!("Choose a password: ");
+                io::stdout().flush().unwrap(); // Prompt for password
+                let mut password = String::new();
+                io::stdin().read_line(&mut password).unwrap(); // Reads the password
+                post_manager.register(username.trim().to_string(), password.trim().to_string()); // Registers the user
+            }
+            2 => {
+                print!("Enter username: ");
+                io::stdout().flush().unwrap(); // Prompt for username
+                let mut username = String::new();
+                io::stdin().read_line(&mut username).unwrap(); // Reads the username
+
+                print!("Enter password: ");
+                io::stdout().flush().unwrap(); // Prompt for password
+                let mut password = String::new();
+                io::stdin().read_line(&mut password).unwrap(); // Reads the password
+                post_manager.login(username.trim().to_string(), password.trim().to_string()); // Logs the user in
+            }
+            3 => {
+                print!("Enter community name: ");
+                io::stdout().flush().unwrap(); // Prompt for community name
+                let mut name = String::new();
+                io::stdin().read_line(&mut name).unwrap(); // Reads the community name
+
+                println!("Categories: {}", DEFAULT_CATEGORIES.join(", "));
+                print!("Enter category: ");
+                io::stdout().flush().unwrap(); // Prompt for category
+                let mut category = String::new();
+                io::stdin().read_line(&mut category).unwrap(); // Reads the category
+                if let Some(community_id) =
+                    post_manager.create_community(name.trim().to_string(), category.trim().to_string())
+                {
+                    println!("Community created with ID: {}", community_id); // Confirms creation
+                }
+            }
+            4 => {
+                post_manager.list_communities(); // Lists all communities
+            }
+            5 => {
+                print!("Enter community ID to post in: ");
+                io::stdout().flush().unwrap(); // Prompt for community ID
+                let mut community_id = String::new();
+                io::stdin().read_line(&mut community_id).unwrap(); // Reads the community ID
+                let community_id: usize = community_id.trim().parse().unwrap_or(0); // Parses community ID
+
                 print!("Enter post content: ");
                 io::stdout().flush().unwrap(); // Prompt for post content
                 let mut content = String::new();
                 io::stdin().read_line(&mut content).unwrap(); // Reads the post content
-                let post_id = post_manager.create_post(content.trim().to_string()); // Creates a post
-                println!("Post created with ID: {}", post_id); // Confirms creation
+                if let Some(post_id) =
+                    post_manager.create_post(content.trim().to_string(), community_id)
+                {
+                    println!("Post created with ID: {}", post_id); // Confirms creation
+                }
             }
-            2 => {
+            6 => {
                 print!("Enter post ID to comment on: ");
                 io::stdout().flush().unwrap(); // Prompt for post ID
                 let mut post_id = String::new();
@@ -186,9 +747,24 @@ fn main() {
                 io::stdout().flush().unwrap(); // Prompt for comment
                 let mut comment = String::new();
                 io::stdin().read_line(&mut comment).unwrap(); // Reads the comment
-                post_manager.add_comment(post_id, comment.trim().to_string()); // Adds the comment
+
+                print!("Enter parent comment ID to reply to (blank for top-level): ");
+                io::stdout().flush().unwrap(); // Prompt for optional parent comment
+                let mut parent_id = String::new();
+                io::stdin().read_line(&mut parent_id).unwrap(); // Reads the parent comment ID
+                let parent_id: Option<usize> = parent_id.trim().parse().ok();
+
+                post_manager.add_reply(post_id, parent_id, comment.trim().to_string()); // Adds the comment/reply
             }
-            3 => {
+            7 => {
+                print!("Enter post ID to view comments on: ");
+                io::stdout().flush().unwrap(); // Prompt for post ID
+                let mut post_id = String::new();
+                io::stdin().read_line(&mut post_id).unwrap(); // Reads the post ID
+                let post_id: usize = post_id.trim().parse().unwrap_or(0); // Parses post ID
+                post_manager.display_comments(post_id); // Prints the comment tree
+            }
+            8 => {
                 print!("Enter post ID to like: ");
                 io::stdout().flush().unwrap(); // Prompt for post ID
                 let mut post_id = String::new();
@@ -196,7 +772,7 @@ fn main() {
                 let post_id: usize = post_id.trim().parse().unwrap_or(0); // Parses post ID
                 post_manager.like_post(post_id); // Likes the post
             }
-            4 => {
+            9 => {
                 print!("Enter post ID to dislike: ");
                 io::stdout().flush().unwrap(); // Prompt for post ID
                 let mut post_id = String::new();
@@ -204,7 +780,7 @@ fn main() {
                 let post_id: usize = post_id.trim().parse().unwrap_or(0); // Parses post ID
                 post_manager.dislike_post(post_id); // Dislikes the post
             }
-            5 => {
+            10 => {
                 print!("Enter post ID to share: ");
                 io::stdout().flush().unwrap(); // Prompt for post ID
                 let mut post_id = String::new();
@@ -212,10 +788,41 @@ fn main() {
                 let post_id: usize = post_id.trim().parse().unwrap_or(0); // Parses post ID
                 post_manager.share_post(post_id); // Shares the post
             }
-            6 => {
-                post_manager.display_posts(); // Displays all posts
+            11 => {
+                println!("Sort by: 1. New  2. Top  3. Hot");
+                print!("Enter your choice: ");
+                io::stdout().flush().unwrap();
+                let mut sort_choice = String::new();
+                io::stdin().read_line(&mut sort_choice).unwrap();
+                let mode = match sort_choice.trim() {
+                    "2" => SortMode::Top,
+                    "3" => SortMode::Hot,
+                    _ => SortMode::New,
+                };
+
+                print!("Filter by community ID (blank for all): ");
+                io::stdout().flush().unwrap();
+                let mut community_id = String::new();
+                io::stdin().read_line(&mut community_id).unwrap();
+                let community_id: Option<usize> = community_id.trim().parse().ok();
+
+                post_manager.display_posts_sorted(mode, community_id); // Displays posts in the chosen order
             }
-            7 => {
+            12 => {
+                print!("Enter post ID to edit: ");
+                io::stdout().flush().unwrap(); // Prompt for post ID
+                let mut post_id = String::new();
+                io::stdin().read_line(&mut post_id).unwrap(); // Reads the post ID
+                let post_id: usize = post_id.trim().parse().unwrap_or(0); // Parses post ID
+
+                print!("Enter new content: ");
+                io::stdout().flush().unwrap(); // Prompt for new content
+                let mut content = String::new();
+                io::stdin().read_line(&mut content).unwrap(); // Reads the new content
+
+                post_manager.edit_post(post_id, content.trim().to_string()); // Edits the post
+            }
+            13 => {
                 print!("Enter post ID to delete: ");
                 io::stdout().flush().unwrap(); // Prompt for post ID
                 let mut post_id = String::new();
@@ -223,7 +830,34 @@ fn main() {
                 let post_id: usize = post_id.trim().parse().unwrap_or(0); // Parses post ID
                 post_manager.delete_post(post_id); // Deletes the post
             }
-            8 => {
+            14 => {
+                print!("Enter post ID to repost: ");
+                io::stdout().flush().unwrap(); // Prompt for post ID
+                let mut post_id = String::new();
+                io::stdin().read_line(&mut post_id).unwrap(); // Reads the post ID
+                let post_id: usize = post_id.trim().parse().unwrap_or(0); // Parses post ID
+                post_manager.repost_post(post_id); // Reposts the post
+            }
+            15 => {
+                print!("Enter post ID to favorite: ");
+                io::stdout().flush().unwrap(); // Prompt for post ID
+                let mut post_id = String::new();
+                io::stdin().read_line(&mut post_id).unwrap(); // Reads the post ID
+                let post_id: usize = post_id.trim().parse().unwrap_or(0); // Parses post ID
+                post_manager.favorite_post(post_id); // Saves the post to favorites
+            }
+            16 => {
+                print!("Enter post ID to unfavorite: ");
+                io::stdout().flush().unwrap(); // Prompt for post ID
+                let mut post_id = String::new();
+                io::stdin().read_line(&mut post_id).unwrap(); // Reads the post ID
+                let post_id: usize = post_id.trim().parse().unwrap_or(0); // Parses post ID
+                post_manager.unfavorite_post(post_id); // Removes the post from favorites
+            }
+            17 => {
+                post_manager.list_favorites(); // Lists the current user's saved posts
+            }
+            18 => {
                 break; // Exits the loop to terminate the program
             }
             _ => {
@@ -232,3 +866,244 @@ fn main() {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn post_with(likes: usize, dislikes: usize, created_at: i64) -> Post {
+        Post {
+            id: 1,
+            content: String::new(),
+            comments: Vec::new(),
+            next_comment_id: 1,
+            likes,
+            dislikes,
+            created_at,
+            author_id: 1,
+            community_id: 1,
+            edit_history: Vec::new(),
+            updated_at: None,
+            reposts: 0,
+            shared_by: Vec::new(),
+        }
+    }
+
+    fn new_post_manager() -> PostManager {
+        PostManager {
+            posts: HashMap::new(),
+            next_id: 1,
+            users: HashMap::new(),
+            next_user_id: 1,
+            current_user: None,
+            communities: HashMap::new(),
+            next_community_id: 1,
+            storage: Box::new(storage::MemoryStorage::new()),
+        }
+    }
+
+    #[test]
+    fn hot_rank_is_zero_at_epoch_offset_when_tied() {
+        // likes == dislikes is the sign == 0 branch; at the epoch offset the
+        // recency term is also zero, so the rank should land exactly on zero.
+        let post = post_with(3, 3, HOT_EPOCH_OFFSET);
+        assert_eq!(post.hot_rank(), 0.0);
+    }
+
+    #[test]
+    fn hot_rank_breaks_ties_by_recency() {
+        let older = post_with(2, 2, HOT_EPOCH_OFFSET);
+        let newer = post_with(2, 2, HOT_EPOCH_OFFSET + 45_000);
+        assert!(newer.hot_rank() > older.hot_rank());
+    }
+
+    #[test]
+    fn hot_rank_rewards_higher_score_at_the_same_time() {
+        let low = post_with(1, 0, HOT_EPOCH_OFFSET);
+        let high = post_with(100, 0, HOT_EPOCH_OFFSET);
+        assert!(high.hot_rank() > low.hot_rank());
+    }
+
+    #[test]
+    fn hot_rank_lets_a_well_liked_old_post_outrank_a_fresh_neutral_post() {
+        let old_but_loved = post_with(1000, 0, HOT_EPOCH_OFFSET);
+        let new_but_neutral = post_with(0, 0, HOT_EPOCH_OFFSET + 3_600);
+        assert!(old_but_loved.hot_rank() > new_but_neutral.hot_rank());
+    }
+
+    #[test]
+    fn register_then_login_succeeds_with_the_right_password() {
+        let mut post_manager = new_post_manager();
+        assert!(post_manager.register("alice".to_string(), "hunter2".to_string()));
+        assert!(post_manager.login("alice".to_string(), "hunter2".to_string()));
+    }
+
+    #[test]
+    fn register_rejects_a_duplicate_username() {
+        let mut post_manager = new_post_manager();
+        assert!(post_manager.register("alice".to_string(), "hunter2".to_string()));
+        assert!(!post_manager.register("alice".to_string(), "different".to_string()));
+    }
+
+    #[test]
+    fn login_fails_with_the_wrong_password() {
+        let mut post_manager = new_post_manager();
+        post_manager.register("alice".to_string(), "hunter2".to_string());
+        assert!(!post_manager.login("alice".to_string(), "wrong".to_string()));
+    }
+
+    #[test]
+    fn only_the_author_may_delete_their_post() {
+        let mut post_manager = new_post_manager();
+        post_manager.register("alice".to_string(), "hunter2".to_string());
+        post_manager.login("alice".to_string(), "hunter2".to_string());
+        let community_id = post_manager
+            .create_community("Rustlang".to_string(), "Science/Technology".to_string())
+            .unwrap();
+        let post_id = post_manager
+            .create_post("hello".to_string(), community_id)
+            .unwrap();
+
+        post_manager.register("bob".to_string(), "swordfish".to_string());
+        post_manager.login("bob".to_string(), "swordfish".to_string());
+        post_manager.delete_post(post_id);
+        assert!(post_manager.posts.contains_key(&post_id));
+
+        post_manager.login("alice".to_string(), "hunter2".to_string());
+        post_manager.delete_post(post_id);
+        assert!(!post_manager.posts.contains_key(&post_id));
+    }
+
+    #[test]
+    fn create_community_rejects_an_unrecognized_category() {
+        let mut post_manager = new_post_manager();
+        assert!(post_manager
+            .create_community("Nonsense".to_string(), "Not A Category".to_string())
+            .is_none());
+    }
+
+    #[test]
+    fn create_post_requires_an_existing_community() {
+        let mut post_manager = new_post_manager();
+        post_manager.register("alice".to_string(), "hunter2".to_string());
+        post_manager.login("alice".to_string(), "hunter2".to_string());
+        assert!(post_manager.create_post("hello".to_string(), 999).is_none());
+    }
+
+    #[test]
+    fn add_reply_assigns_increasing_ids_and_threads_by_parent() {
+        let mut post = post_with(0, 0, HOT_EPOCH_OFFSET);
+        let top_level = post.add_reply("first".to_string(), None, 1);
+        let reply = post.add_reply("a reply".to_string(), Some(top_level), 2);
+        let nested_reply = post.add_reply("a nested reply".to_string(), Some(reply), 1);
+
+        assert_eq!(post.comments.len(), 3);
+        assert_eq!(top_level, 1);
+        assert_eq!(reply, 2);
+        assert_eq!(nested_reply, 3);
+
+        // The tree can be reconstructed by following `parent_id` links.
+        let children_of =
+            |parent: Option<usize>| -> Vec<usize> {
+                post.comments
+                    .iter()
+                    .filter(|c| c.parent_id == parent)
+                    .map(|c| c.id)
+                    .collect()
+            };
+        assert_eq!(children_of(None), vec![top_level]);
+        assert_eq!(children_of(Some(top_level)), vec![reply]);
+        assert_eq!(children_of(Some(reply)), vec![nested_reply]);
+        assert_eq!(children_of(Some(nested_reply)), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn editing_a_post_records_history_and_updated_timestamp() {
+        let mut post_manager = new_post_manager();
+        post_manager.register("alice".to_string(), "hunter2".to_string());
+        post_manager.login("alice".to_string(), "hunter2".to_string());
+        let community_id = post_manager
+            .create_community("Rustlang".to_string(), "Science/Technology".to_string())
+            .unwrap();
+        let post_id = post_manager
+            .create_post("original".to_string(), community_id)
+            .unwrap();
+
+        assert!(post_manager.edit_post(post_id, "updated".to_string()));
+
+        let post = &post_manager.posts[&post_id];
+        assert_eq!(post.content, "updated");
+        assert_eq!(post.edit_history, vec!["original".to_string()]);
+        assert!(post.updated_at.is_some());
+    }
+
+    #[test]
+    fn only_the_author_may_edit_their_post() {
+        let mut post_manager = new_post_manager();
+        post_manager.register("alice".to_string(), "hunter2".to_string());
+        post_manager.login("alice".to_string(), "hunter2".to_string());
+        let community_id = post_manager
+            .create_community("Rustlang".to_string(), "Science/Technology".to_string())
+            .unwrap();
+        let post_id = post_manager
+            .create_post("original".to_string(), community_id)
+            .unwrap();
+
+        post_manager.register("bob".to_string(), "swordfish".to_string());
+        post_manager.login("bob".to_string(), "swordfish".to_string());
+        assert!(!post_manager.edit_post(post_id, "hijacked".to_string()));
+        assert_eq!(post_manager.posts[&post_id].content, "original");
+    }
+
+    #[test]
+    fn reposting_increments_the_counter_and_records_the_sharer() {
+        let mut post_manager = new_post_manager();
+        post_manager.register("alice".to_string(), "hunter2".to_string());
+        post_manager.login("alice".to_string(), "hunter2".to_string());
+        let community_id = post_manager
+            .create_community("Rustlang".to_string(), "Science/Technology".to_string())
+            .unwrap();
+        let post_id = post_manager
+            .create_post("hello".to_string(), community_id)
+            .unwrap();
+
+        post_manager.register("bob".to_string(), "swordfish".to_string());
+        let bob_id = post_manager.login("bob".to_string(), "swordfish".to_string());
+        assert!(bob_id);
+        assert!(post_manager.repost_post(post_id));
+
+        let post = &post_manager.posts[&post_id];
+        assert_eq!(post.reposts, 1);
+        assert_eq!(post.shared_by, vec![post_manager.current_user.unwrap()]);
+    }
+
+    #[test]
+    fn favoriting_and_unfavoriting_a_post_round_trips() {
+        let mut post_manager = new_post_manager();
+        post_manager.register("alice".to_string(), "hunter2".to_string());
+        post_manager.login("alice".to_string(), "hunter2".to_string());
+        let community_id = post_manager
+            .create_community("Rustlang".to_string(), "Science/Technology".to_string())
+            .unwrap();
+        let post_id = post_manager
+            .create_post("hello".to_string(), community_id)
+            .unwrap();
+
+        assert!(post_manager.favorite_post(post_id));
+        let user_id = post_manager.current_user.unwrap();
+        assert_eq!(post_manager.users[&user_id].favorites, vec![post_id]);
+
+        // Favoriting the same post twice is rejected, not duplicated.
+        assert!(!post_manager.favorite_post(post_id));
+        assert_eq!(post_manager.users[&user_id].favorites, vec![post_id]);
+
+        assert!(post_manager.unfavorite_post(post_id));
+        assert!(post_manager.users[&user_id].favorites.is_empty());
+    }
+
+    #[test]
+    fn favoriting_requires_a_logged_in_user() {
+        let mut post_manager = new_post_manager();
+        assert!(!post_manager.favorite_post(1));
+    }
+}
@@ -0,0 +1,258 @@
+// An optional WebSocket JSON API server that lets multiple clients share one
+// `PostManager` concurrently, instead of the single-user stdin menu loop.
+use std::sync::{Arc, Mutex};
+
+use futures_util::{SinkExt, StreamExt};
+use serde::Deserialize;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::PostManager;
+
+// State shared across every connected client.
+type SharedManager = Arc<Mutex<PostManager>>;
+
+// An operation requested by a client, parsed from an envelope of the shape
+// `{ "op": "CreatePost", "data": { ... } }`. Mutating operations never carry
+// a `user_id` — the acting user comes only from this connection's own
+// `Login`-established session, so one client can never act as another.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "op", content = "data")]
+enum Operation {
+    Login {
+        username: String,
+        password: String,
+    },
+    CreatePost {
+        content: String,
+        community_id: usize,
+    },
+    AddComment {
+        post_id: usize,
+        parent_id: Option<usize>,
+        content: String,
+    },
+    LikePost {
+        post_id: usize,
+    },
+    DislikePost {
+        post_id: usize,
+    },
+    EditPost {
+        post_id: usize,
+        content: String,
+    },
+    DeletePost {
+        post_id: usize,
+    },
+    RepostPost {
+        post_id: usize,
+    },
+    FavoritePost {
+        post_id: usize,
+    },
+    UnfavoritePost {
+        post_id: usize,
+    },
+    GetPosts,
+    GetFavorites,
+}
+
+// Runs the WebSocket server, accepting connections forever until it errors.
+pub async fn run(addr: &str) -> std::io::Result<()> {
+    let manager: SharedManager = Arc::new(Mutex::new(PostManager::new()));
+    let (broadcast_tx, _) = broadcast::channel::<String>(100);
+
+    let listener = TcpListener::bind(addr).await?;
+    println!("WebSocket API server listening on {}", addr);
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let manager = Arc::clone(&manager);
+        let broadcast_tx = broadcast_tx.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(stream, manager, broadcast_tx).await {
+                eprintln!("WebSocket connection error: {}", e);
+            }
+        });
+    }
+}
+
+// Drives a single client connection: replies to queries directly and relays
+// every broadcast update (from this client or any other) to the socket. Each
+// connection tracks its own logged-in user, established via a `Login`
+// operation, so mutating operations always act as the connection's own user.
+async fn handle_connection(
+    stream: TcpStream,
+    manager: SharedManager,
+    broadcast_tx: broadcast::Sender<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let ws_stream = tokio_tungstenite::accept_async(stream).await?;
+    let (mut writer, mut reader) = ws_stream.split();
+    let mut updates = broadcast_tx.subscribe();
+    let mut session_user: Option<usize> = None;
+
+    loop {
+        tokio::select! {
+            incoming = reader.next() => {
+                match incoming {
+                    Some(Ok(Message::Text(text))) => {
+                        if let Some(reply) = handle_message(&text, &manager, &broadcast_tx, &mut session_user) {
+                            writer.send(Message::Text(reply.into())).await?;
+                        }
+                    }
+                    Some(Ok(Message::Close(_))) | None => break,
+                    Some(Err(e)) => {
+                        eprintln!("WebSocket read error: {}", e);
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+            update = updates.recv() => {
+                if let Ok(payload) = update {
+                    writer.send(Message::Text(payload.into())).await?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Parses and dispatches one client message. Returns a direct reply for
+// queries, `Login`, malformed requests and failed mutations; a successful
+// mutation is broadcast instead.
+fn handle_message(
+    text: &str,
+    manager: &SharedManager,
+    broadcast_tx: &broadcast::Sender<String>,
+    session_user: &mut Option<usize>,
+) -> Option<String> {
+    let operation: Operation = match serde_json::from_str(text) {
+        Ok(operation) => operation,
+        Err(e) => return Some(serde_json::json!({ "error": e.to_string() }).to_string()),
+    };
+
+    if matches!(operation, Operation::GetPosts) {
+        let post_manager = manager.lock().unwrap();
+        let posts: Vec<&crate::Post> = post_manager.posts.values().collect();
+        return Some(serde_json::to_string(&posts).unwrap_or_default());
+    }
+
+    if matches!(operation, Operation::GetFavorites) {
+        let post_manager = manager.lock().unwrap();
+        let posts: Vec<&crate::Post> = match session_user.and_then(|id| post_manager.users.get(&id)) {
+            Some(user) => user
+                .favorites
+                .iter()
+                .filter_map(|post_id| post_manager.posts.get(post_id))
+                .collect(),
+            None => Vec::new(),
+        };
+        return Some(serde_json::to_string(&posts).unwrap_or_default());
+    }
+
+    if let Operation::Login { username, password } = operation {
+        let mut post_manager = manager.lock().unwrap();
+        return Some(if post_manager.login(username, password) {
+            *session_user = post_manager.current_user;
+            serde_json::json!({ "logged_in": true }).to_string()
+        } else {
+            serde_json::json!({ "logged_in": false }).to_string()
+        });
+    }
+
+    let mut post_manager = manager.lock().unwrap();
+    post_manager.current_user = *session_user;
+    match apply_operation(operation, &mut post_manager) {
+        Ok(post_id) => {
+            let payload = match post_manager.posts.get(&post_id) {
+                Some(post) => serde_json::to_string(post).unwrap_or_default(),
+                None => serde_json::json!({ "deleted_post_id": post_id }).to_string(),
+            };
+            let _ = broadcast_tx.send(payload); // No receivers is not an error; nobody is listening yet.
+            None
+        }
+        Err(e) => Some(serde_json::json!({ "error": e }).to_string()),
+    }
+}
+
+// Dispatches an operation to the existing `PostManager` methods, returning
+// the ID of the post that changed (or was created/deleted) so the caller can
+// look up its current state to broadcast. The manager's `current_user` must
+// already be set to this connection's session before calling this.
+fn apply_operation(op: Operation, post_manager: &mut PostManager) -> Result<usize, String> {
+    match op {
+        Operation::Login { .. } | Operation::GetPosts | Operation::GetFavorites => {
+            unreachable!("handled before locking the manager")
+        }
+        Operation::CreatePost {
+            content,
+            community_id,
+        } => post_manager
+            .create_post(content, community_id)
+            .ok_or_else(|| "failed to create post".to_string()),
+        Operation::AddComment {
+            post_id,
+            parent_id,
+            content,
+        } => {
+            if post_manager.add_reply(post_id, parent_id, content) {
+                Ok(post_id)
+            } else {
+                Err("failed to add comment".to_string())
+            }
+        }
+        Operation::LikePost { post_id } => {
+            if post_manager.like_post(post_id) {
+                Ok(post_id)
+            } else {
+                Err("post not found".to_string())
+            }
+        }
+        Operation::DislikePost { post_id } => {
+            if post_manager.dislike_post(post_id) {
+                Ok(post_id)
+            } else {
+                Err("post not found".to_string())
+            }
+        }
+        Operation::EditPost { post_id, content } => {
+            if post_manager.edit_post(post_id, content) {
+                Ok(post_id)
+            } else {
+                Err("failed to edit post".to_string())
+            }
+        }
+        Operation::DeletePost { post_id } => {
+            if post_manager.delete_post(post_id) {
+                Ok(post_id)
+            } else {
+                Err("failed to delete post".to_string())
+            }
+        }
+        Operation::RepostPost { post_id } => {
+            if post_manager.repost_post(post_id) {
+                Ok(post_id)
+            } else {
+                Err("failed to repost".to_string())
+            }
+        }
+        Operation::FavoritePost { post_id } => {
+            if post_manager.favorite_post(post_id) {
+                Ok(post_id)
+            } else {
+                Err("failed to favorite post".to_string())
+            }
+        }
+        Operation::UnfavoritePost { post_id } => {
+            if post_manager.unfavorite_post(post_id) {
+                Ok(post_id)
+            } else {
+                Err("failed to unfavorite post".to_string())
+            }
+        }
+    }
+}
@@ -0,0 +1,122 @@
+// Pluggable persistence for `PostManager`, so the JSON-file-on-disk
+// behavior is just one implementation and can be swapped (e.g. for tests,
+// or a future database-backed store) without touching `PostManager` itself.
+use std::fs;
+use std::sync::Arc;
+#[cfg(test)]
+use std::sync::Mutex;
+
+use crate::PostManager;
+
+// Loads and saves a `PostManager`'s full state as a single unit. `Send` is
+// required because a `PostManager` (and therefore its storage backend) is
+// shared across the WebSocket server's per-connection tasks.
+pub trait Storage: Send {
+    // Loads the persisted state, or `None` if there is nothing to load yet
+    // (first run) or the existing data couldn't be read.
+    fn load(&self) -> Option<PostManager>;
+
+    // Persists the given state, overwriting whatever was there before.
+    fn save(&self, post_manager: &PostManager);
+}
+
+// Stores state as a single JSON file on disk. This is the default storage
+// backend used outside of tests.
+pub struct FileStorage {
+    path: String,
+}
+
+impl FileStorage {
+    pub fn new(path: impl Into<String>) -> FileStorage {
+        FileStorage { path: path.into() }
+    }
+}
+
+impl Default for FileStorage {
+    fn default() -> FileStorage {
+        FileStorage::new("posts.json")
+    }
+}
+
+impl Storage for FileStorage {
+    fn load(&self) -> Option<PostManager> {
+        let data = fs::read_to_string(&self.path).ok()?;
+        serde_json::from_str(&data).ok()
+    }
+
+    fn save(&self, post_manager: &PostManager) {
+        if let Ok(json_data) = serde_json::to_string(post_manager) {
+            let _ = fs::write(&self.path, json_data); // Writes JSON data to the file
+        }
+    }
+}
+
+// Lets an `Arc<T>` be used as a storage backend directly, so tests can keep a
+// handle to the same backend a `PostManager` is using.
+impl<T: Storage + Sync> Storage for Arc<T> {
+    fn load(&self) -> Option<PostManager> {
+        (**self).load()
+    }
+
+    fn save(&self, post_manager: &PostManager) {
+        (**self).save(post_manager)
+    }
+}
+
+// In-memory storage backend for tests: holds the last-saved JSON in memory
+// instead of touching disk.
+#[cfg(test)]
+#[derive(Default)]
+pub struct MemoryStorage {
+    data: Mutex<Option<String>>,
+}
+
+#[cfg(test)]
+impl MemoryStorage {
+    pub fn new() -> MemoryStorage {
+        MemoryStorage::default()
+    }
+}
+
+#[cfg(test)]
+impl Storage for MemoryStorage {
+    fn load(&self) -> Option<PostManager> {
+        let data = self.data.lock().unwrap();
+        serde_json::from_str(data.as_ref()?).ok()
+    }
+
+    fn save(&self, post_manager: &PostManager) {
+        if let Ok(json_data) = serde_json::to_string(post_manager) {
+            *self.data.lock().unwrap() = Some(json_data);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memory_storage_load_is_none_before_first_save() {
+        let storage = MemoryStorage::new();
+        assert!(storage.load().is_none());
+    }
+
+    #[test]
+    fn create_post_persists_through_memory_storage() {
+        let storage = Arc::new(MemoryStorage::new());
+
+        let mut post_manager = PostManager::with_storage(Box::new(Arc::clone(&storage)));
+        post_manager.register("alice".to_string(), "hunter2".to_string());
+        post_manager.login("alice".to_string(), "hunter2".to_string());
+        let community_id = post_manager
+            .create_community("Rustlang".to_string(), "Science/Technology".to_string())
+            .unwrap();
+        let post_id = post_manager
+            .create_post("hello".to_string(), community_id)
+            .unwrap();
+
+        let reloaded = PostManager::with_storage(Box::new(Arc::clone(&storage)));
+        assert!(reloaded.posts.contains_key(&post_id));
+    }
+}